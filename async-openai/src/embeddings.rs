@@ -0,0 +1,180 @@
+use futures::stream::{self, StreamExt};
+
+use crate::{
+    config::Config,
+    error::OpenAIError,
+    types::{CreateEmbeddingRequest, CreateEmbeddingResponse, EmbeddingUsage},
+    Client,
+};
+
+/// The largest `input` array the `/embeddings` endpoint accepts in a single request.
+const MAX_BATCH_SIZE: usize = 2048;
+
+/// Given a client, create embeddings for text prompts, and embed over batches larger than
+/// OpenAI's per-request input limit.
+pub struct Embeddings<'c, C: Config> {
+    client: &'c Client<C>,
+}
+
+impl<'c, C: Config> Embeddings<'c, C> {
+    pub fn new(client: &'c Client<C>) -> Self {
+        Self { client }
+    }
+
+    /// Creates an embedding vector representing the input text.
+    pub async fn create(
+        &self,
+        request: CreateEmbeddingRequest,
+    ) -> Result<CreateEmbeddingResponse, OpenAIError> {
+        self.client.post("/embeddings", request).await
+    }
+
+    /// Embeds an `input` of any size by splitting it into chunks of at most
+    /// [`MAX_BATCH_SIZE`] entries, dispatching up to `concurrency` chunk requests at a time,
+    /// and stitching the responses back together.
+    ///
+    /// The merged response's `data` is re-indexed and sorted so it lines up with `request.input`
+    /// in its original order, and `usage` is the sum across all chunk responses.
+    pub async fn create_batched(
+        &self,
+        request: CreateEmbeddingRequest,
+        concurrency: usize,
+    ) -> Result<CreateEmbeddingResponse, OpenAIError> {
+        let CreateEmbeddingRequest {
+            model,
+            input,
+            encoding_format,
+            user,
+            dimensions,
+        } = request;
+
+        let chunks = input.into_chunks(MAX_BATCH_SIZE);
+
+        let mut offset = 0usize;
+        let offsets: Vec<usize> = chunks
+            .iter()
+            .map(|chunk| {
+                let start = offset;
+                offset += chunk.len();
+                start
+            })
+            .collect();
+
+        let responses: Vec<Result<(usize, CreateEmbeddingResponse), OpenAIError>> =
+            stream::iter(chunks.into_iter().zip(offsets))
+                .map(|(input, offset)| {
+                    // Only the (small) non-`input` fields need cloning per chunk; `input` itself
+                    // is already the chunk's own slice of the original, already-split data.
+                    let chunk_request = CreateEmbeddingRequest {
+                        model: model.clone(),
+                        input,
+                        encoding_format: encoding_format.clone(),
+                        user: user.clone(),
+                        dimensions,
+                    };
+                    async move {
+                        self.create(chunk_request)
+                            .await
+                            .map(|response| (offset, response))
+                    }
+                })
+                .buffer_unordered(concurrency.max(1))
+                .collect()
+                .await;
+
+        let responses = responses
+            .into_iter()
+            .collect::<Result<Vec<_>, OpenAIError>>()?;
+
+        Ok(merge_chunk_responses(responses))
+    }
+}
+
+/// Stitches per-chunk `(offset, response)` pairs back into a single response, re-indexing each
+/// chunk's embeddings by its offset and sorting the merged `data` so it lines up with the
+/// original `input` order, regardless of which chunk request completed first. `usage` is summed
+/// across all chunks; `object`/`model` are taken from whichever chunk is merged last, since
+/// they're the same across chunks of one request.
+fn merge_chunk_responses(
+    responses: Vec<(usize, CreateEmbeddingResponse)>,
+) -> CreateEmbeddingResponse {
+    let mut data = Vec::new();
+    let mut usage = EmbeddingUsage {
+        prompt_tokens: 0,
+        total_tokens: 0,
+    };
+    let mut object = String::new();
+    let mut model = String::new();
+
+    for (offset, response) in responses {
+        usage.prompt_tokens += response.usage.prompt_tokens;
+        usage.total_tokens += response.usage.total_tokens;
+        object = response.object;
+        model = response.model;
+        data.extend(response.data.into_iter().map(|mut embedding| {
+            embedding.index += offset as u32;
+            embedding
+        }));
+    }
+
+    data.sort_by_key(|embedding| embedding.index);
+
+    CreateEmbeddingResponse {
+        object,
+        model,
+        data,
+        usage,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Embedding, EmbeddingVector};
+
+    fn response(object: &str, model: &str, indices: &[u32]) -> CreateEmbeddingResponse {
+        CreateEmbeddingResponse {
+            object: object.to_string(),
+            model: model.to_string(),
+            data: indices
+                .iter()
+                .map(|&index| Embedding {
+                    index,
+                    object: "embedding".to_string(),
+                    embedding: EmbeddingVector::Float(vec![index as f32]),
+                })
+                .collect(),
+            usage: EmbeddingUsage {
+                prompt_tokens: indices.len() as u32,
+                total_tokens: indices.len() as u32,
+            },
+        }
+    }
+
+    #[test]
+    fn merge_chunk_responses_preserves_input_order_when_chunks_arrive_out_of_order() {
+        // Second chunk (offset 2) completes before the first (offset 0).
+        let responses = vec![
+            (2, response("list", "text-embedding-3-small", &[0, 1])),
+            (0, response("list", "text-embedding-3-small", &[0, 1])),
+        ];
+
+        let merged = merge_chunk_responses(responses);
+
+        let indices: Vec<u32> = merged.data.iter().map(|e| e.index).collect();
+        assert_eq!(indices, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn merge_chunk_responses_sums_usage_across_chunks() {
+        let responses = vec![
+            (0, response("list", "text-embedding-3-small", &[0, 1])),
+            (2, response("list", "text-embedding-3-small", &[0])),
+        ];
+
+        let merged = merge_chunk_responses(responses);
+
+        assert_eq!(merged.usage.prompt_tokens, 3);
+        assert_eq!(merged.usage.total_tokens, 3);
+    }
+}
@@ -0,0 +1,50 @@
+use derive_builder::UninitializedFieldError;
+use serde::Deserialize;
+
+/// An error that occurred while communicating with the OpenAI API, or while preparing
+/// or reading a request/response.
+#[derive(Debug, thiserror::Error)]
+pub enum OpenAIError {
+    /// Underlying error from reqwest library after an API call was made
+    #[error("http error: {0}")]
+    Reqwest(#[from] reqwest::Error),
+    /// OpenAI returns error object with details of API call failure
+    #[error("{}", .0.message)]
+    ApiError(ApiError),
+    /// Error when a response cannot be deserialized into a Rust type
+    #[error("failed to deserialize api response: {0}")]
+    JSONDeserialize(serde_json::Error),
+    /// Error on the client side when saving file to disk
+    #[error("failed to save file: {0}")]
+    FileSaveError(String),
+    /// Error on the client side when reading file from disk
+    #[error("failed to read file: {0}")]
+    FileReadError(String),
+    /// Error when streaming response from OpenAI
+    #[error("stream failed: {0}")]
+    StreamError(String),
+    /// Error when building request payload before it is sent to OpenAI
+    #[error("invalid args: {0}")]
+    InvalidArgument(String),
+    /// Error when two vectors expected to be the same length (e.g. for a similarity
+    /// computation) are not
+    #[error("vector length mismatch: left has {left} dimensions, right has {right}")]
+    VectorLengthMismatch { left: usize, right: usize },
+    /// Error decoding a base64-encoded embedding vector
+    #[error("failed to decode base64 embedding: {0}")]
+    Base64Decode(#[from] base64::DecodeError),
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ApiError {
+    pub message: String,
+    pub r#type: Option<String>,
+    pub param: Option<serde_json::Value>,
+    pub code: Option<serde_json::Value>,
+}
+
+impl From<UninitializedFieldError> for OpenAIError {
+    fn from(value: UninitializedFieldError) -> Self {
+        OpenAIError::InvalidArgument(value.to_string())
+    }
+}
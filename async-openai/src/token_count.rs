@@ -0,0 +1,202 @@
+//! Token counting for embedding inputs, backed by [`tiktoken_rs`]. Gated behind the
+//! `tokenizer` feature so that consumers who don't need it aren't forced to pull in the BPE
+//! tables.
+#![cfg(feature = "tokenizer")]
+
+use tiktoken_rs::CoreBPE;
+
+use crate::error::OpenAIError;
+use crate::types::{EmbeddingInput, EmbeddingModel};
+
+/// What [`enforce_limit`] (and `CreateEmbeddingRequestArgs::truncate_to_model_limit`) should do
+/// with an input that's over the model's token limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnOverflow {
+    /// Truncate the input down to the model's limit.
+    Truncate,
+    /// Reject the request with an [`OpenAIError::InvalidArgument`].
+    Error,
+}
+
+/// All current embedding models (`ada-002`, `3-small`, `3-large`) are tokenized with the
+/// `cl100k_base` encoding.
+fn bpe_for(_model: &EmbeddingModel) -> Result<CoreBPE, OpenAIError> {
+    tiktoken_rs::cl100k_base().map_err(|e| OpenAIError::InvalidArgument(e.to_string()))
+}
+
+/// Counts how many tokens `text` would cost against `model`.
+pub fn count_tokens(model: &EmbeddingModel, text: &str) -> Result<usize, OpenAIError> {
+    Ok(bpe_for(model)?.encode_with_special_tokens(text).len())
+}
+
+/// Truncates `text` to at most `max_tokens` tokens of `model`'s encoding, re-decoding the kept
+/// tokens back to a `String`.
+pub(crate) fn truncate_to_tokens(
+    model: &EmbeddingModel,
+    text: &str,
+    max_tokens: usize,
+) -> Result<String, OpenAIError> {
+    let bpe = bpe_for(model)?;
+    let tokens = bpe.encode_with_special_tokens(text);
+    if tokens.len() <= max_tokens {
+        return Ok(text.to_string());
+    }
+
+    bpe.decode(tokens[..max_tokens].to_vec())
+        .map_err(|e| OpenAIError::InvalidArgument(e.to_string()))
+}
+
+/// Applies `on_overflow` to every entry of `input` that exceeds `max_tokens` for `model`.
+pub(crate) fn enforce_limit(
+    input: &mut EmbeddingInput,
+    model: &EmbeddingModel,
+    max_tokens: usize,
+    on_overflow: OnOverflow,
+) -> Result<(), OpenAIError> {
+    let too_long = |len: usize| -> Result<(), OpenAIError> {
+        if on_overflow == OnOverflow::Error && len > max_tokens {
+            Err(OpenAIError::InvalidArgument(format!(
+                "input has {len} tokens, which exceeds the {max_tokens} token limit of {}",
+                model.name()
+            )))
+        } else {
+            Ok(())
+        }
+    };
+
+    match input {
+        EmbeddingInput::String(text) => {
+            too_long(count_tokens(model, text)?)?;
+            *text = truncate_to_tokens(model, text, max_tokens)?;
+        }
+        EmbeddingInput::StringArray(texts) => {
+            for text in texts.iter_mut() {
+                too_long(count_tokens(model, text)?)?;
+                *text = truncate_to_tokens(model, text, max_tokens)?;
+            }
+        }
+        EmbeddingInput::IntegerArray(tokens) => {
+            too_long(tokens.len())?;
+            tokens.truncate(max_tokens);
+        }
+        EmbeddingInput::ArrayOfIntegerArray(arrays) => {
+            for tokens in arrays.iter_mut() {
+                too_long(tokens.len())?;
+                tokens.truncate(max_tokens);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integer_array_truncates_on_overflow() {
+        let mut input = EmbeddingInput::IntegerArray((0..10).collect());
+        enforce_limit(
+            &mut input,
+            &EmbeddingModel::TextEmbeddingAda002,
+            5,
+            OnOverflow::Truncate,
+        )
+        .unwrap();
+
+        assert_eq!(input, EmbeddingInput::IntegerArray((0..5).collect()));
+    }
+
+    #[test]
+    fn integer_array_errors_on_overflow() {
+        let mut input = EmbeddingInput::IntegerArray((0..10).collect());
+        let err = enforce_limit(
+            &mut input,
+            &EmbeddingModel::TextEmbeddingAda002,
+            5,
+            OnOverflow::Error,
+        )
+        .unwrap_err();
+
+        assert!(matches!(err, OpenAIError::InvalidArgument(_)));
+    }
+
+    #[test]
+    fn array_of_integer_array_truncates_each_entry_on_overflow() {
+        let mut input =
+            EmbeddingInput::ArrayOfIntegerArray(vec![(0..10).collect(), (0..3).collect()]);
+        enforce_limit(
+            &mut input,
+            &EmbeddingModel::TextEmbeddingAda002,
+            5,
+            OnOverflow::Truncate,
+        )
+        .unwrap();
+
+        assert_eq!(
+            input,
+            EmbeddingInput::ArrayOfIntegerArray(vec![(0..5).collect(), (0..3).collect()])
+        );
+    }
+
+    #[test]
+    fn array_of_integer_array_errors_if_any_entry_overflows() {
+        let mut input =
+            EmbeddingInput::ArrayOfIntegerArray(vec![(0..3).collect(), (0..10).collect()]);
+        let err = enforce_limit(
+            &mut input,
+            &EmbeddingModel::TextEmbeddingAda002,
+            5,
+            OnOverflow::Error,
+        )
+        .unwrap_err();
+
+        assert!(matches!(err, OpenAIError::InvalidArgument(_)));
+    }
+
+    #[test]
+    fn string_under_the_limit_is_left_untouched() {
+        let mut input = EmbeddingInput::String("hello world".to_string());
+        enforce_limit(
+            &mut input,
+            &EmbeddingModel::TextEmbeddingAda002,
+            100,
+            OnOverflow::Truncate,
+        )
+        .unwrap();
+
+        assert_eq!(input, EmbeddingInput::String("hello world".to_string()));
+    }
+
+    #[test]
+    fn string_over_the_limit_errors_when_on_overflow_is_error() {
+        let mut input = EmbeddingInput::String("hello world, this is a longer string".to_string());
+        let err = enforce_limit(
+            &mut input,
+            &EmbeddingModel::TextEmbeddingAda002,
+            2,
+            OnOverflow::Error,
+        )
+        .unwrap_err();
+
+        assert!(matches!(err, OpenAIError::InvalidArgument(_)));
+    }
+
+    #[test]
+    fn string_over_the_limit_truncates_when_on_overflow_is_truncate() {
+        let mut input = EmbeddingInput::String("hello world, this is a longer string".to_string());
+        enforce_limit(
+            &mut input,
+            &EmbeddingModel::TextEmbeddingAda002,
+            2,
+            OnOverflow::Truncate,
+        )
+        .unwrap();
+
+        let EmbeddingInput::String(text) = &input else {
+            panic!("expected EmbeddingInput::String");
+        };
+        assert!(count_tokens(&EmbeddingModel::TextEmbeddingAda002, text).unwrap() <= 2);
+    }
+}
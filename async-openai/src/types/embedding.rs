@@ -14,6 +14,36 @@ pub enum EmbeddingInput {
     ArrayOfIntegerArray(Vec<Vec<u32>>),
 }
 
+impl EmbeddingInput {
+    /// Number of entries carried by this input (1 for the single-value variants, otherwise
+    /// the array length).
+    pub(crate) fn len(&self) -> usize {
+        match self {
+            EmbeddingInput::String(_) => 1,
+            EmbeddingInput::StringArray(v) => v.len(),
+            EmbeddingInput::IntegerArray(_) => 1,
+            EmbeddingInput::ArrayOfIntegerArray(v) => v.len(),
+        }
+    }
+
+    /// Splits this input into chunks of at most `chunk_size` entries, preserving order. The
+    /// single-value variants (`String`/`IntegerArray`) are always returned as one chunk, since
+    /// they can't be split further.
+    pub(crate) fn into_chunks(self, chunk_size: usize) -> Vec<EmbeddingInput> {
+        match self {
+            EmbeddingInput::String(_) | EmbeddingInput::IntegerArray(_) => vec![self],
+            EmbeddingInput::StringArray(v) => v
+                .chunks(chunk_size)
+                .map(|c| EmbeddingInput::StringArray(c.to_vec()))
+                .collect(),
+            EmbeddingInput::ArrayOfIntegerArray(v) => v
+                .chunks(chunk_size)
+                .map(|c| EmbeddingInput::ArrayOfIntegerArray(c.to_vec()))
+                .collect(),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Default, Clone, PartialEq, Deserialize, utoipa::ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum EncodingFormat {
@@ -22,18 +52,145 @@ pub enum EncodingFormat {
     Base64,
 }
 
+/// A known embedding model, carrying the token/dimension metadata OpenAI documents for it.
+///
+/// `model` on [`CreateEmbeddingRequest`] is a plain `String` on the wire, but builders can be
+/// given an `EmbeddingModel` directly (it converts via `Into<String>`) to get access to
+/// [`EmbeddingModel::max_tokens`], [`EmbeddingModel::default_dimensions`] and
+/// [`EmbeddingModel::supports_overriding_dimensions`] up front, and to have `dimensions` validated
+/// against the chosen model when the request is built.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum EmbeddingModel {
+    TextEmbeddingAda002,
+    TextEmbedding3Small,
+    TextEmbedding3Large,
+    /// Any model name not recognized above. Builder validation of `dimensions` is skipped for
+    /// this variant since its limits aren't known.
+    Other(String),
+}
+
+impl EmbeddingModel {
+    /// The model name as sent to the API.
+    pub fn name(&self) -> &str {
+        match self {
+            EmbeddingModel::TextEmbeddingAda002 => "text-embedding-ada-002",
+            EmbeddingModel::TextEmbedding3Small => "text-embedding-3-small",
+            EmbeddingModel::TextEmbedding3Large => "text-embedding-3-large",
+            EmbeddingModel::Other(name) => name,
+        }
+    }
+
+    /// Maximum number of input tokens the model accepts.
+    pub fn max_tokens(&self) -> usize {
+        match self {
+            EmbeddingModel::TextEmbeddingAda002 => 8191,
+            EmbeddingModel::TextEmbedding3Small => 8191,
+            EmbeddingModel::TextEmbedding3Large => 8191,
+            EmbeddingModel::Other(_) => usize::MAX,
+        }
+    }
+
+    /// The native output dimensionality of the model.
+    pub fn default_dimensions(&self) -> usize {
+        match self {
+            EmbeddingModel::TextEmbeddingAda002 => 1536,
+            EmbeddingModel::TextEmbedding3Small => 1536,
+            EmbeddingModel::TextEmbedding3Large => 3072,
+            EmbeddingModel::Other(_) => 0,
+        }
+    }
+
+    /// Whether the model allows overriding its native dimensionality via `dimensions`.
+    pub fn supports_overriding_dimensions(&self) -> bool {
+        match self {
+            EmbeddingModel::TextEmbeddingAda002 => false,
+            EmbeddingModel::TextEmbedding3Small => true,
+            EmbeddingModel::TextEmbedding3Large => true,
+            EmbeddingModel::Other(_) => true,
+        }
+    }
+
+    /// Looks up a well-known model by its API name, returning `None` for anything else.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "text-embedding-ada-002" => Some(EmbeddingModel::TextEmbeddingAda002),
+            "text-embedding-3-small" => Some(EmbeddingModel::TextEmbedding3Small),
+            "text-embedding-3-large" => Some(EmbeddingModel::TextEmbedding3Large),
+            _ => None,
+        }
+    }
+
+    /// The distribution shift that recenters this model's raw cosine similarities into a
+    /// well-spread `[0, 1]` relevance score, as observed empirically across corpora. Returns
+    /// `None` for [`EmbeddingModel::Other`], whose score distribution isn't known.
+    pub fn default_distribution_shift(&self) -> Option<DistributionShift> {
+        match self {
+            EmbeddingModel::TextEmbeddingAda002 => Some(DistributionShift {
+                mean: 0.90,
+                sigma: 0.08,
+            }),
+            EmbeddingModel::TextEmbedding3Small => Some(DistributionShift {
+                mean: 0.75,
+                sigma: 0.1,
+            }),
+            EmbeddingModel::TextEmbedding3Large => Some(DistributionShift {
+                mean: 0.75,
+                sigma: 0.1,
+            }),
+            EmbeddingModel::Other(_) => None,
+        }
+    }
+}
+
+/// Parameters for recentering a raw cosine similarity score into a more evenly spread `[0, 1]`
+/// relevance score. See [`EmbeddingVector::cosine_similarity_shifted`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DistributionShift {
+    /// The mean of the raw similarity scores observed for this model/corpus.
+    pub mean: f32,
+    /// The standard deviation of the raw similarity scores observed for this model/corpus.
+    pub sigma: f32,
+}
+
+/// Approximates the Gauss error function via the Abramowitz–Stegun rational polynomial
+/// (formula 7.1.26), accurate to within `1.5e-7`.
+fn erf(x: f32) -> f32 {
+    let sign = if x < 0.0 { -1.0 } else { 1.0 };
+    let x = x.abs();
+
+    const A1: f32 = 0.254829592;
+    const A2: f32 = -0.284496736;
+    const A3: f32 = 1.421413741;
+    const A4: f32 = -1.453152027;
+    const A5: f32 = 1.061405429;
+    const P: f32 = 0.3275911;
+
+    let t = 1.0 / (1.0 + P * x);
+    let poly = ((((A5 * t + A4) * t + A3) * t + A2) * t + A1) * t;
+    sign * (1.0 - poly * (-x * x).exp())
+}
+
+impl From<EmbeddingModel> for String {
+    fn from(value: EmbeddingModel) -> Self {
+        match value {
+            EmbeddingModel::Other(name) => name,
+            known => known.name().to_string(),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Default, Clone, Builder, PartialEq, Deserialize, utoipa::ToSchema)]
 #[builder(name = "CreateEmbeddingRequestArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
 #[builder(derive(Debug))]
-#[builder(build_fn(error = "OpenAIError"))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct CreateEmbeddingRequest {
     /// ID of the model to use. You can use the
     /// [List models](https://platform.openai.com/docs/api-reference/models/list)
     /// API to see all of your available models, or see our
     /// [Model overview](https://platform.openai.com/docs/models/overview)
-    /// for descriptions of them.
+    /// for descriptions of them. Accepts either a model name or an [`EmbeddingModel`].
     pub model: String,
 
     ///  Input text to embed, encoded as a string or array of tokens. To embed multiple inputs in a single request, pass an array of strings or array of token arrays. The input must not exceed the max input tokens for the model (8192 tokens for `text-embedding-ada-002`), cannot be an empty string, and any array must be 2048 dimensions or less. [Example Python code](https://cookbook.openai.com/examples/how_to_count_tokens_with_tiktoken) for counting tokens.
@@ -53,6 +210,66 @@ pub struct CreateEmbeddingRequest {
     pub dimensions: Option<u32>,
 }
 
+impl CreateEmbeddingRequestArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        if let (Some(Some(dimensions)), Some(model)) = (self.dimensions, self.model.as_ref()) {
+            // Unrecognized models have no known limits to validate against.
+            let Some(model) = EmbeddingModel::from_name(model) else {
+                return Ok(());
+            };
+
+            if !model.supports_overriding_dimensions() {
+                return Err(OpenAIError::InvalidArgument(format!(
+                    "{} does not support overriding `dimensions`",
+                    model.name()
+                )));
+            }
+
+            if dimensions as usize > model.default_dimensions() {
+                return Err(OpenAIError::InvalidArgument(format!(
+                    "`dimensions` ({dimensions}) exceeds the maximum of {} for {}",
+                    model.default_dimensions(),
+                    model.name()
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Ensures every entry of `input` fits within `model`'s `max_tokens()`, truncating or
+    /// erroring on overflow depending on `on_overflow`.
+    ///
+    /// For [`EmbeddingInput::String`]/[`EmbeddingInput::StringArray`] this tokenizes each
+    /// string with the [`crate::token_count`] module; for
+    /// [`EmbeddingInput::IntegerArray`]/[`EmbeddingInput::ArrayOfIntegerArray`] the token
+    /// arrays' lengths are checked directly, with no tokenization needed.
+    #[cfg(feature = "tokenizer")]
+    pub fn truncate_to_model_limit(
+        &mut self,
+        on_overflow: crate::token_count::OnOverflow,
+    ) -> Result<&mut Self, OpenAIError> {
+        // Unrecognized models (custom/fine-tuned/Azure deployments, or no model set yet) have no
+        // known token limit; `EmbeddingModel::Other::max_tokens()` is `usize::MAX`, so this is a
+        // no-op rather than a guess at some other model's limit.
+        let model = self
+            .model
+            .as_ref()
+            .map(|name| {
+                EmbeddingModel::from_name(name)
+                    .unwrap_or_else(|| EmbeddingModel::Other(name.clone()))
+            })
+            .unwrap_or_else(|| EmbeddingModel::Other(String::new()));
+        let max_tokens = model.max_tokens();
+
+        if let Some(input) = self.input.as_mut() {
+            crate::token_count::enforce_limit(input, &model, max_tokens, on_overflow)?;
+        }
+
+        Ok(self)
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, utoipa::ToSchema)]
 #[serde(untagged)]
 pub enum EmbeddingVector {
@@ -60,23 +277,51 @@ pub enum EmbeddingVector {
     Base64(String),
 }
 
-impl From<EmbeddingVector> for Vec<f32> {
-    fn from(val: EmbeddingVector) -> Self {
-        match val {
-            EmbeddingVector::Float(v) => v,
+impl EmbeddingVector {
+    /// Decodes this vector to `Vec<f32>`, decoding the base64 payload if necessary.
+    ///
+    /// Returns an [`OpenAIError::Base64Decode`] if the payload isn't valid base64, or an
+    /// [`OpenAIError::InvalidArgument`] if the decoded byte length isn't a multiple of 4
+    /// (the size of an `f32`).
+    fn decoded_floats(&self) -> Result<Vec<f32>, OpenAIError> {
+        match self {
+            EmbeddingVector::Float(v) => Ok(v.clone()),
             EmbeddingVector::Base64(s) => {
-                let bytes = general_purpose::STANDARD
-                    .decode(s)
-                    .expect("openai base64 encoding to be valid");
-                let chunks = bytes.chunks_exact(4);
-                chunks
+                let bytes = general_purpose::STANDARD.decode(s)?;
+                if bytes.len() % 4 != 0 {
+                    return Err(OpenAIError::InvalidArgument(format!(
+                        "decoded embedding is {} bytes, not a multiple of 4",
+                        bytes.len()
+                    )));
+                }
+
+                Ok(bytes
+                    .chunks_exact(4)
                     .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
-                    .collect::<Vec<f32>>()
+                    .collect())
             }
         }
     }
 }
 
+impl TryFrom<EmbeddingVector> for Vec<f32> {
+    type Error = OpenAIError;
+
+    fn try_from(val: EmbeddingVector) -> Result<Self, Self::Error> {
+        val.decoded_floats()
+    }
+}
+
+impl From<EmbeddingVector> for Vec<f32> {
+    /// Panics if `val` is a [`EmbeddingVector::Base64`] payload that isn't valid base64, or
+    /// whose decoded length isn't a multiple of 4. Prefer `Vec::<f32>::try_from` when parsing
+    /// responses from an untrusted or proxied source.
+    fn from(val: EmbeddingVector) -> Self {
+        val.decoded_floats()
+            .expect("openai base64 encoding to be valid")
+    }
+}
+
 /// Converts an embedding vector to a base64-encoded string.
 impl From<EmbeddingVector> for String {
     fn from(val: EmbeddingVector) -> Self {
@@ -106,14 +351,85 @@ impl EmbeddingVector {
     pub fn len(&self) -> usize {
         match self {
             EmbeddingVector::Float(v) => v.len(),
-            EmbeddingVector::Base64(v) => {
-                let bytes = general_purpose::STANDARD
-                    .decode(v)
-                    .expect("openai base64 encoding to be valid");
-                bytes.len() / 4
-            }
+            // Falls back to 0 rather than panicking on malformed base64; use
+            // `decoded_floats` directly if the distinction matters.
+            EmbeddingVector::Base64(_) => self.decoded_floats().map_or(0, |v| v.len()),
         }
     }
+
+    /// Dot product of this vector with `other`.
+    pub fn dot(&self, other: &EmbeddingVector) -> Result<f32, OpenAIError> {
+        let a = self.decoded_floats()?;
+        let b = other.decoded_floats()?;
+
+        if a.len() != b.len() {
+            return Err(OpenAIError::VectorLengthMismatch {
+                left: a.len(),
+                right: b.len(),
+            });
+        }
+
+        Ok(a.iter().zip(b.iter()).map(|(x, y)| x * y).sum())
+    }
+
+    /// Euclidean (L2) norm of this vector.
+    pub fn l2_norm(&self) -> Result<f32, OpenAIError> {
+        Ok(self
+            .decoded_floats()?
+            .iter()
+            .map(|x| x * x)
+            .sum::<f32>()
+            .sqrt())
+    }
+
+    /// Cosine similarity between this vector and `other`. Returns `0.0` rather than `NaN`
+    /// when either vector has a zero norm.
+    pub fn cosine_similarity(&self, other: &EmbeddingVector) -> Result<f32, OpenAIError> {
+        let a = self.decoded_floats()?;
+        let b = other.decoded_floats()?;
+
+        if a.len() != b.len() {
+            return Err(OpenAIError::VectorLengthMismatch {
+                left: a.len(),
+                right: b.len(),
+            });
+        }
+
+        let (dot, norm_a, norm_b) = a.iter().zip(b.iter()).fold(
+            (0f32, 0f32, 0f32),
+            |(dot, norm_a, norm_b), (x, y)| (dot + x * y, norm_a + x * x, norm_b + y * y),
+        );
+
+        let denom = norm_a.sqrt() * norm_b.sqrt();
+        if denom == 0.0 {
+            return Ok(0.0);
+        }
+
+        Ok(dot / denom)
+    }
+
+    /// Recenters a raw [`cosine_similarity`](Self::cosine_similarity) through `shift` so that
+    /// scores are more evenly spread over `[0, 1]`: `z = (s - shift.mean) / shift.sigma`, then
+    /// `0.5 * (1 + erf(z / sqrt(2)))`, clamped to `[0, 1]`.
+    ///
+    /// Returns [`OpenAIError::InvalidArgument`] if `shift.sigma` isn't positive, since dividing
+    /// by a zero sigma would make `z` infinite or `NaN`, and a negative one would flip its sign.
+    pub fn cosine_similarity_shifted(
+        &self,
+        other: &EmbeddingVector,
+        shift: &DistributionShift,
+    ) -> Result<f32, OpenAIError> {
+        if shift.sigma <= 0.0 {
+            return Err(OpenAIError::InvalidArgument(format!(
+                "DistributionShift::sigma must be positive, got {}",
+                shift.sigma
+            )));
+        }
+
+        let s = self.cosine_similarity(other)?;
+        let z = (s - shift.mean) / shift.sigma;
+        Ok((0.5 * (1.0 + erf(z / std::f32::consts::SQRT_2))).clamp(0.0, 1.0))
+    }
 }
 
 /// Represents an embedding vector returned by embedding endpoint.
@@ -146,3 +462,219 @@ pub struct CreateEmbeddingResponse {
     /// The usage information for the request.
     pub usage: EmbeddingUsage,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_name_looks_up_known_models() {
+        assert_eq!(
+            EmbeddingModel::from_name("text-embedding-ada-002"),
+            Some(EmbeddingModel::TextEmbeddingAda002)
+        );
+        assert_eq!(
+            EmbeddingModel::from_name("text-embedding-3-small"),
+            Some(EmbeddingModel::TextEmbedding3Small)
+        );
+        assert_eq!(
+            EmbeddingModel::from_name("text-embedding-3-large"),
+            Some(EmbeddingModel::TextEmbedding3Large)
+        );
+        assert_eq!(EmbeddingModel::from_name("some-custom-model"), None);
+    }
+
+    #[test]
+    fn max_tokens_and_default_dimensions_match_documented_values() {
+        assert_eq!(EmbeddingModel::TextEmbeddingAda002.max_tokens(), 8191);
+        assert_eq!(EmbeddingModel::TextEmbeddingAda002.default_dimensions(), 1536);
+        assert_eq!(EmbeddingModel::TextEmbedding3Small.max_tokens(), 8191);
+        assert_eq!(EmbeddingModel::TextEmbedding3Small.default_dimensions(), 1536);
+        assert_eq!(EmbeddingModel::TextEmbedding3Large.max_tokens(), 8191);
+        assert_eq!(EmbeddingModel::TextEmbedding3Large.default_dimensions(), 3072);
+
+        let other = EmbeddingModel::Other("some-custom-model".to_string());
+        assert_eq!(other.max_tokens(), usize::MAX);
+        assert_eq!(other.default_dimensions(), 0);
+    }
+
+    #[test]
+    fn supports_overriding_dimensions_matches_documented_values() {
+        assert!(!EmbeddingModel::TextEmbeddingAda002.supports_overriding_dimensions());
+        assert!(EmbeddingModel::TextEmbedding3Small.supports_overriding_dimensions());
+        assert!(EmbeddingModel::TextEmbedding3Large.supports_overriding_dimensions());
+        assert!(EmbeddingModel::Other("some-custom-model".to_string())
+            .supports_overriding_dimensions());
+    }
+
+    #[test]
+    fn build_rejects_dimensions_override_when_model_does_not_support_it() {
+        let err = CreateEmbeddingRequestArgs::default()
+            .model(EmbeddingModel::TextEmbeddingAda002)
+            .input(EmbeddingInput::String("hello".to_string()))
+            .dimensions(256u32)
+            .build()
+            .unwrap_err();
+
+        assert!(matches!(err, OpenAIError::InvalidArgument(_)));
+    }
+
+    #[test]
+    fn build_rejects_dimensions_exceeding_default_dimensions() {
+        let err = CreateEmbeddingRequestArgs::default()
+            .model(EmbeddingModel::TextEmbedding3Small)
+            .input(EmbeddingInput::String("hello".to_string()))
+            .dimensions(1537u32)
+            .build()
+            .unwrap_err();
+
+        assert!(matches!(err, OpenAIError::InvalidArgument(_)));
+    }
+
+    #[test]
+    fn build_succeeds_when_dimensions_equals_default_dimensions() {
+        CreateEmbeddingRequestArgs::default()
+            .model(EmbeddingModel::TextEmbedding3Small)
+            .input(EmbeddingInput::String("hello".to_string()))
+            .dimensions(EmbeddingModel::TextEmbedding3Small.default_dimensions() as u32)
+            .build()
+            .unwrap();
+    }
+
+    #[test]
+    fn build_skips_validation_for_unrecognized_models() {
+        CreateEmbeddingRequestArgs::default()
+            .model("some-custom-model")
+            .input(EmbeddingInput::String("hello".to_string()))
+            .dimensions(u32::MAX)
+            .build()
+            .unwrap();
+    }
+
+    #[test]
+    fn dot_rejects_length_mismatch() {
+        let a = EmbeddingVector::Float(vec![1.0, 2.0]);
+        let b = EmbeddingVector::Float(vec![1.0, 2.0, 3.0]);
+
+        let err = a.dot(&b).unwrap_err();
+        assert!(matches!(
+            err,
+            OpenAIError::VectorLengthMismatch { left: 2, right: 3 }
+        ));
+    }
+
+    #[test]
+    fn cosine_similarity_rejects_length_mismatch() {
+        let a = EmbeddingVector::Float(vec![1.0, 2.0]);
+        let b = EmbeddingVector::Float(vec![1.0, 2.0, 3.0]);
+
+        let err = a.cosine_similarity(&b).unwrap_err();
+        assert!(matches!(
+            err,
+            OpenAIError::VectorLengthMismatch { left: 2, right: 3 }
+        ));
+    }
+
+    #[test]
+    fn dot_matches_hand_computed_value() {
+        let a = EmbeddingVector::Float(vec![1.0, 2.0, 3.0]);
+        let b = EmbeddingVector::Float(vec![4.0, 5.0, 6.0]);
+
+        // 1*4 + 2*5 + 3*6 = 32
+        assert_eq!(a.dot(&b).unwrap(), 32.0);
+    }
+
+    #[test]
+    fn l2_norm_matches_hand_computed_value() {
+        let v = EmbeddingVector::Float(vec![3.0, 4.0]);
+
+        assert_eq!(v.l2_norm().unwrap(), 5.0);
+    }
+
+    #[test]
+    fn cosine_similarity_matches_hand_computed_value() {
+        let a = EmbeddingVector::Float(vec![1.0, 0.0]);
+        let b = EmbeddingVector::Float(vec![0.0, 1.0]);
+
+        assert_eq!(a.cosine_similarity(&b).unwrap(), 0.0);
+
+        let a = EmbeddingVector::Float(vec![1.0, 0.0]);
+        let b = EmbeddingVector::Float(vec![2.0, 0.0]);
+
+        assert_eq!(a.cosine_similarity(&b).unwrap(), 1.0);
+    }
+
+    #[test]
+    fn cosine_similarity_is_zero_for_zero_norm_vector() {
+        let a = EmbeddingVector::Float(vec![0.0, 0.0]);
+        let b = EmbeddingVector::Float(vec![1.0, 1.0]);
+
+        assert_eq!(a.cosine_similarity(&b).unwrap(), 0.0);
+    }
+
+    #[test]
+    fn try_from_reports_malformed_base64_as_an_error() {
+        let v = EmbeddingVector::Base64("not valid base64!!".to_string());
+
+        let result: Result<Vec<f32>, OpenAIError> = v.try_into();
+        assert!(matches!(result, Err(OpenAIError::Base64Decode(_))));
+    }
+
+    #[test]
+    #[should_panic(expected = "openai base64 encoding to be valid")]
+    fn from_panics_on_malformed_base64() {
+        let v = EmbeddingVector::Base64("not valid base64!!".to_string());
+
+        let _: Vec<f32> = v.into();
+    }
+
+    #[test]
+    fn try_from_reports_wrong_byte_length_as_an_error() {
+        // Valid base64 (3 bytes), but not a multiple of 4.
+        let v = EmbeddingVector::Base64(general_purpose::STANDARD.encode(b"abc"));
+
+        let result: Result<Vec<f32>, OpenAIError> = v.try_into();
+        assert!(matches!(result, Err(OpenAIError::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn try_from_and_from_agree_on_valid_base64() {
+        let floats = vec![1.0f32, -2.5, 3.0];
+        let mut bytes = Vec::new();
+        for f in &floats {
+            bytes.extend_from_slice(&f.to_le_bytes());
+        }
+        let encoded = EmbeddingVector::Base64(general_purpose::STANDARD.encode(&bytes));
+
+        let via_try_from: Vec<f32> = encoded.clone().try_into().unwrap();
+        let via_from: Vec<f32> = encoded.into();
+        assert_eq!(via_try_from, floats);
+        assert_eq!(via_from, floats);
+    }
+
+    #[test]
+    fn cosine_similarity_shifted_rejects_non_positive_sigma() {
+        let a = EmbeddingVector::Float(vec![1.0, 0.0]);
+        let b = EmbeddingVector::Float(vec![1.0, 0.0]);
+        let shift = DistributionShift {
+            mean: 0.9,
+            sigma: 0.0,
+        };
+
+        let err = a.cosine_similarity_shifted(&b, &shift).unwrap_err();
+        assert!(matches!(err, OpenAIError::InvalidArgument(_)));
+    }
+
+    #[test]
+    fn cosine_similarity_shifted_stays_within_unit_range() {
+        let a = EmbeddingVector::Float(vec![1.0, 0.0]);
+        let b = EmbeddingVector::Float(vec![1.0, 0.0]);
+        let shift = DistributionShift {
+            mean: 0.75,
+            sigma: 0.1,
+        };
+
+        let score = a.cosine_similarity_shifted(&b, &shift).unwrap();
+        assert!((0.0..=1.0).contains(&score));
+    }
+}